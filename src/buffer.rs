@@ -0,0 +1,152 @@
+//! Sound, stack-allocated storage for received strokes.
+//!
+//! [`crate::Interception::receive`] historically filled a plain
+//! `[Stroke; N]` that started out `MaybeUninit::uninit().assume_init()` —
+//! immediate undefined behavior, since `Stroke` is an enum with no valid
+//! all-uninitialized representation. `StrokeBuffer` stores its slots as
+//! `MaybeUninit<Stroke>` instead, so a slot only ever becomes a `Stroke`
+//! once something has actually written one into it, and only the written
+//! prefix is ever exposed.
+
+use std::mem::MaybeUninit;
+use std::ops::Deref;
+
+use crate::Stroke;
+
+/// Fixed-capacity, stack-allocated buffer of up to `N` strokes that never
+/// holds an uninitialized `Stroke` value.
+pub struct StrokeBuffer<const N: usize> {
+    data: [MaybeUninit<Stroke>; N],
+    len: usize,
+}
+
+impl<const N: usize> StrokeBuffer<N> {
+    pub fn new() -> Self {
+        StrokeBuffer {
+            // Safety: `[MaybeUninit<Stroke>; N]` has no validity
+            // requirements of its own, so leaving every slot uninitialized
+            // is fine here (unlike `[Stroke; N]`).
+            data: unsafe { MaybeUninit::uninit().assume_init() },
+            len: 0,
+        }
+    }
+
+    /// The strokes written into this buffer so far.
+    pub fn as_slice(&self) -> &[Stroke] {
+        // Safety: slots `0..self.len` are always written by `push` before
+        // `len` is advanced past them.
+        unsafe { std::slice::from_raw_parts(self.data.as_ptr().cast(), self.len) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    pub(crate) fn push(&mut self, stroke: Stroke) {
+        debug_assert!(self.len < N, "StrokeBuffer is full");
+        self.data[self.len] = MaybeUninit::new(stroke);
+        self.len += 1;
+    }
+}
+
+impl<const N: usize> Default for StrokeBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Deref for StrokeBuffer<N> {
+    type Target = [Stroke];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MouseFlags, MouseState};
+
+    fn sentinel(x: i32) -> Stroke {
+        Stroke::Mouse {
+            state: MouseState::empty(),
+            flags: MouseFlags::empty(),
+            rolling: 0,
+            x,
+            y: 0,
+            information: 0,
+        }
+    }
+
+    #[test]
+    fn new_buffer_is_empty() {
+        let buffer = StrokeBuffer::<4>::new();
+        assert_eq!(buffer.len(), 0);
+        assert!(buffer.is_empty());
+        assert!(buffer.as_slice().is_empty());
+    }
+
+    #[test]
+    fn push_appends_and_is_visible_through_as_slice() {
+        let mut buffer = StrokeBuffer::<4>::new();
+        buffer.push(sentinel(1));
+        assert_eq!(buffer.len(), 1);
+        assert!(!buffer.is_empty());
+
+        let Stroke::Mouse { x, .. } = buffer.as_slice()[0] else {
+            panic!("expected a mouse stroke");
+        };
+        assert_eq!(x, 1);
+    }
+
+    #[test]
+    fn push_up_to_capacity_keeps_every_stroke_in_order() {
+        let mut buffer = StrokeBuffer::<3>::new();
+        for i in 0..3 {
+            buffer.push(sentinel(i));
+        }
+        assert_eq!(buffer.len(), 3);
+
+        let xs: Vec<i32> = buffer
+            .as_slice()
+            .iter()
+            .map(|stroke| match stroke {
+                Stroke::Mouse { x, .. } => *x,
+                Stroke::Keyboard { .. } => unreachable!(),
+            })
+            .collect();
+        assert_eq!(xs, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn clear_resets_len_without_touching_capacity() {
+        let mut buffer = StrokeBuffer::<2>::new();
+        buffer.push(sentinel(1));
+        buffer.clear();
+        assert_eq!(buffer.len(), 0);
+        assert!(buffer.as_slice().is_empty());
+
+        buffer.push(sentinel(2));
+        assert_eq!(buffer.len(), 1);
+    }
+
+    #[test]
+    fn deref_exposes_the_same_strokes_as_as_slice() {
+        let mut buffer = StrokeBuffer::<2>::new();
+        buffer.push(sentinel(5));
+        assert_eq!(buffer.len(), (*buffer).len());
+        match buffer[0] {
+            Stroke::Mouse { x, .. } => assert_eq!(x, 5),
+            Stroke::Keyboard { .. } => unreachable!(),
+        }
+    }
+}
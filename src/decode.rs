@@ -0,0 +1,165 @@
+//! Semantic decoding of raw [`Stroke`]s into named keys.
+//!
+//! [`decode_key`] combines a stroke's base scancode with its `E0`/`E1`
+//! prefix bits into a [`Key`], so callers can match on `Key::Left` instead
+//! of reconstructing it from scancode and state bits themselves. The raw
+//! API is untouched.
+
+use crate::{KeyState, Stroke};
+
+/// A named key, combining the base scancode with its `E0` prefix where that
+/// distinguishes two physical keys (e.g. the arrow keys vs. the numpad keys
+/// sharing their scancodes). Left and right modifiers are not told apart:
+/// both decode to the same `Ctrl`, `Alt` or `Shift`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    F(u8),
+    Backspace,
+    Tab,
+    Enter,
+    Escape,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+    Ctrl,
+    Alt,
+    Shift,
+}
+
+/// A decoded key press or release.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub key: Key,
+    pub pressed: bool,
+}
+
+/// Decodes a raw `Stroke` into a [`KeyEvent`], or `None` for mouse strokes
+/// and scancodes with no known mapping.
+///
+/// `E1`-prefixed strokes (the Pause/Break key's synthetic sequence) aren't
+/// decoded yet and return `None` rather than being matched against the
+/// non-extended table, which would misreport them as whatever plain key
+/// happens to share that scancode.
+pub fn decode_key(stroke: Stroke) -> Option<KeyEvent> {
+    let (code, state) = match stroke {
+        Stroke::Keyboard { code, state, .. } => (code as u16, state),
+        Stroke::Mouse { .. } => return None,
+    };
+
+    if state.contains(KeyState::E1) {
+        return None;
+    }
+
+    let extended = state.contains(KeyState::E0);
+    let pressed = !state.contains(KeyState::UP);
+    let key = named_key(code, extended)?;
+
+    Some(KeyEvent { key, pressed })
+}
+
+fn named_key(code: u16, extended: bool) -> Option<Key> {
+    if extended {
+        return Some(match code {
+            0x1C => Key::Enter,
+            0x1D => Key::Ctrl,
+            0x38 => Key::Alt,
+            0x47 => Key::Home,
+            0x48 => Key::Up,
+            0x49 => Key::PageUp,
+            0x4B => Key::Left,
+            0x4D => Key::Right,
+            0x4F => Key::End,
+            0x50 => Key::Down,
+            0x51 => Key::PageDown,
+            0x52 => Key::Insert,
+            0x53 => Key::Delete,
+            _ => return None,
+        });
+    }
+
+    Some(match code {
+        0x01 => Key::Escape,
+        0x0E => Key::Backspace,
+        0x0F => Key::Tab,
+        0x1C => Key::Enter,
+        0x1D => Key::Ctrl,
+        0x2A | 0x36 => Key::Shift,
+        0x38 => Key::Alt,
+        0x3B..=0x44 => Key::F(code as u8 - 0x3B + 1),
+        0x57 => Key::F(11),
+        0x58 => Key::F(12),
+        _ => Key::Char(crate::keymap::char_for_scancode(code)?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::*;
+    use crate::{MouseFlags, MouseState, ScanCode};
+
+    fn keyboard_stroke(code: u16, state: KeyState) -> Stroke {
+        Stroke::Keyboard {
+            code: ScanCode::try_from(code).unwrap_or(ScanCode::Esc),
+            state,
+            information: 0,
+        }
+    }
+
+    #[test]
+    fn plain_left_ctrl_decodes_as_pressed() {
+        let event = decode_key(keyboard_stroke(0x1D, KeyState::DOWN)).unwrap();
+        assert_eq!(
+            event,
+            KeyEvent {
+                key: Key::Ctrl,
+                pressed: true,
+            }
+        );
+    }
+
+    #[test]
+    fn e0_prefixed_code_decodes_as_the_extended_key() {
+        let event = decode_key(keyboard_stroke(0x4B, KeyState::E0)).unwrap();
+        assert_eq!(event.key, Key::Left);
+    }
+
+    #[test]
+    fn up_state_is_reported_as_released() {
+        let event = decode_key(keyboard_stroke(0x1E, KeyState::UP)).unwrap();
+        assert!(!event.pressed);
+    }
+
+    #[test]
+    fn printable_scancode_decodes_to_its_char() {
+        let event = decode_key(keyboard_stroke(0x1E, KeyState::DOWN)).unwrap();
+        assert_eq!(event.key, Key::Char('a'));
+    }
+
+    #[test]
+    fn e1_prefixed_stroke_is_not_decoded() {
+        assert_eq!(decode_key(keyboard_stroke(0x1D, KeyState::E1)), None);
+    }
+
+    #[test]
+    fn mouse_strokes_are_not_decoded() {
+        let stroke = Stroke::Mouse {
+            state: MouseState::empty(),
+            flags: MouseFlags::empty(),
+            rolling: 0,
+            x: 0,
+            y: 0,
+            information: 0,
+        };
+        assert_eq!(decode_key(stroke), None);
+    }
+}
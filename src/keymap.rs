@@ -0,0 +1,210 @@
+//! US-layout scancode table used by [`crate::Interception::type_str`] to
+//! turn text into keyboard strokes.
+
+/// The PS/2 Set 1 scancode for the left shift key.
+pub(crate) const LEFT_SHIFT: u16 = 0x2A;
+
+/// Looks up the `(scancode, shift)` pair needed to type `c` on a US
+/// keyboard. Returns `None` for characters with no US-layout mapping.
+pub(crate) fn lookup(c: char) -> Option<(u16, bool)> {
+    let mapping = match c {
+        'a'..='z' => (letter(c), false),
+        'A'..='Z' => (letter(c.to_ascii_lowercase()), true),
+
+        '1' => (0x02, false),
+        '2' => (0x03, false),
+        '3' => (0x04, false),
+        '4' => (0x05, false),
+        '5' => (0x06, false),
+        '6' => (0x07, false),
+        '7' => (0x08, false),
+        '8' => (0x09, false),
+        '9' => (0x0A, false),
+        '0' => (0x0B, false),
+
+        '!' => (0x02, true),
+        '@' => (0x03, true),
+        '#' => (0x04, true),
+        '$' => (0x05, true),
+        '%' => (0x06, true),
+        '^' => (0x07, true),
+        '&' => (0x08, true),
+        '*' => (0x09, true),
+        '(' => (0x0A, true),
+        ')' => (0x0B, true),
+
+        '-' => (0x0C, false),
+        '_' => (0x0C, true),
+        '=' => (0x0D, false),
+        '+' => (0x0D, true),
+
+        '[' => (0x1A, false),
+        '{' => (0x1A, true),
+        ']' => (0x1B, false),
+        '}' => (0x1B, true),
+        '\\' => (0x2B, false),
+        '|' => (0x2B, true),
+
+        ';' => (0x27, false),
+        ':' => (0x27, true),
+        '\'' => (0x28, false),
+        '"' => (0x28, true),
+        '`' => (0x29, false),
+        '~' => (0x29, true),
+
+        ',' => (0x33, false),
+        '<' => (0x33, true),
+        '.' => (0x34, false),
+        '>' => (0x34, true),
+        '/' => (0x35, false),
+        '?' => (0x35, true),
+
+        ' ' => (0x39, false),
+        '\t' => (0x0F, false),
+        '\n' => (0x1C, false),
+
+        _ => return None,
+    };
+
+    Some(mapping)
+}
+
+/// Reverse lookup of [`lookup`]: the unshifted US-layout character for a
+/// base scancode, used by [`crate::decode`] to translate printable keys.
+pub(crate) fn char_for_scancode(code: u16) -> Option<char> {
+    let c = match code {
+        0x10 => 'q',
+        0x11 => 'w',
+        0x12 => 'e',
+        0x13 => 'r',
+        0x14 => 't',
+        0x15 => 'y',
+        0x16 => 'u',
+        0x17 => 'i',
+        0x18 => 'o',
+        0x19 => 'p',
+        0x1E => 'a',
+        0x1F => 's',
+        0x20 => 'd',
+        0x21 => 'f',
+        0x22 => 'g',
+        0x23 => 'h',
+        0x24 => 'j',
+        0x25 => 'k',
+        0x26 => 'l',
+        0x2C => 'z',
+        0x2D => 'x',
+        0x2E => 'c',
+        0x2F => 'v',
+        0x30 => 'b',
+        0x31 => 'n',
+        0x32 => 'm',
+
+        0x02 => '1',
+        0x03 => '2',
+        0x04 => '3',
+        0x05 => '4',
+        0x06 => '5',
+        0x07 => '6',
+        0x08 => '7',
+        0x09 => '8',
+        0x0A => '9',
+        0x0B => '0',
+
+        0x0C => '-',
+        0x0D => '=',
+        0x1A => '[',
+        0x1B => ']',
+        0x2B => '\\',
+        0x27 => ';',
+        0x28 => '\'',
+        0x29 => '`',
+        0x33 => ',',
+        0x34 => '.',
+        0x35 => '/',
+        0x39 => ' ',
+
+        _ => return None,
+    };
+
+    Some(c)
+}
+
+/// Scancode for a lowercase `'a'..='z'` letter, laid out by US keyboard
+/// position rather than alphabetical order.
+fn letter(c: char) -> u16 {
+    match c {
+        'q' => 0x10,
+        'w' => 0x11,
+        'e' => 0x12,
+        'r' => 0x13,
+        't' => 0x14,
+        'y' => 0x15,
+        'u' => 0x16,
+        'i' => 0x17,
+        'o' => 0x18,
+        'p' => 0x19,
+        'a' => 0x1E,
+        's' => 0x1F,
+        'd' => 0x20,
+        'f' => 0x21,
+        'g' => 0x22,
+        'h' => 0x23,
+        'j' => 0x24,
+        'k' => 0x25,
+        'l' => 0x26,
+        'z' => 0x2C,
+        'x' => 0x2D,
+        'c' => 0x2E,
+        'v' => 0x2F,
+        'b' => 0x30,
+        'n' => 0x31,
+        'm' => 0x32,
+        _ => unreachable!("letter() is only called with 'a'..='z'"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unshifted_chars_round_trip_through_char_for_scancode() {
+        let unshifted = ('a'..='z')
+            .chain('0'..='9')
+            .chain(['-', '=', '[', ']', '\\', ';', '\'', '`', ',', '.', '/', ' ']);
+
+        for c in unshifted {
+            let (code, shift) = lookup(c).unwrap_or_else(|| panic!("no mapping for {:?}", c));
+            assert!(!shift, "{:?} should not require shift", c);
+            assert_eq!(char_for_scancode(code), Some(c));
+        }
+    }
+
+    #[test]
+    fn shifted_letters_share_the_unshifted_scancode() {
+        for c in 'A'..='Z' {
+            let (code, shift) = lookup(c).unwrap();
+            assert!(shift);
+            assert_eq!(char_for_scancode(code), Some(c.to_ascii_lowercase()));
+        }
+    }
+
+    #[test]
+    fn tab_and_newline_have_no_reverse_mapping() {
+        assert_eq!(lookup('\t'), Some((0x0F, false)));
+        assert_eq!(lookup('\n'), Some((0x1C, false)));
+        assert_eq!(char_for_scancode(0x0F), None);
+        assert_eq!(char_for_scancode(0x1C), None);
+    }
+
+    #[test]
+    fn unmapped_char_returns_none() {
+        assert_eq!(lookup('€'), None);
+    }
+
+    #[test]
+    fn unmapped_scancode_returns_none() {
+        assert_eq!(char_for_scancode(0xFFFF), None);
+    }
+}
@@ -5,14 +5,21 @@ extern crate bitflags;
 
 pub use interception_sys as raw;
 pub mod bounded_slice;
+pub mod buffer;
+pub mod decode;
+pub mod keymap;
 pub mod scancode;
+#[cfg(feature = "futures")]
+pub mod stream;
 
 pub use bounded_slice::{BoundedSlice, BoundedSliceSource};
+pub use buffer::StrokeBuffer;
 pub use scancode::ScanCode;
+#[cfg(feature = "futures")]
+pub use stream::StrokeStream;
 
 use std::char::decode_utf16;
 use std::convert::{TryFrom, TryInto};
-use std::default::Default;
 use std::mem::MaybeUninit;
 use std::ops::{Index, IndexMut};
 use std::time::Duration;
@@ -20,6 +27,7 @@ use std::time::Duration;
 pub type Device = i32;
 pub type Precedence = i32;
 
+#[derive(Debug, Copy, Clone)]
 pub enum Filter {
     MouseFilter(MouseFilter),
     KeyFilter(KeyFilter),
@@ -74,7 +82,7 @@ bitflags! {
         const UP = 1;
 
         const E0 = 2;
-        const E1 = 3;
+        const E1 = 4;
 
         const TERMSRV_SET_LED = 8;
         const TERMSRV_SHADOW = 16;
@@ -129,8 +137,8 @@ impl TryFrom<raw::InterceptionMouseStroke> for Stroke {
         };
 
         Ok(Stroke::Mouse {
-            state: state,
-            flags: flags,
+            state,
+            flags,
             rolling: raw_stroke.rolling,
             x: raw_stroke.x,
             y: raw_stroke.y,
@@ -154,8 +162,8 @@ impl TryFrom<raw::InterceptionKeyStroke> for Stroke {
         };
 
         Ok(Stroke::Keyboard {
-            code: code,
-            state: state,
+            code,
+            state,
             information: raw_stroke.information,
         })
     }
@@ -177,10 +185,10 @@ impl TryFrom<Stroke> for raw::InterceptionMouseStroke {
             Ok(raw::InterceptionMouseStroke {
                 state: state.bits(),
                 flags: flags.bits(),
-                rolling: rolling,
-                x: x,
-                y: y,
-                information: information,
+                rolling,
+                x,
+                y,
+                information,
             })
         } else {
             Err("Stroke must be a mouse stroke")
@@ -201,7 +209,7 @@ impl TryFrom<Stroke> for raw::InterceptionKeyStroke {
             Ok(raw::InterceptionKeyStroke {
                 code: code as u16,
                 state: state.bits(),
-                information: information,
+                information,
             })
         } else {
             Err("Stroke must be a keyboard stroke")
@@ -225,7 +233,19 @@ where
 
 impl<const BUFFER_SIZE: usize> InterceptionBuffer<BUFFER_SIZE> for [Stroke; BUFFER_SIZE] {
     fn new() -> Self {
-        unsafe { MaybeUninit::uninit().assume_init() }
+        // `Stroke` has no valid all-uninitialized representation, so unlike
+        // the raw FFI stroke types below this can't be built with
+        // `MaybeUninit::uninit().assume_init()`; fill every slot with a real
+        // (if meaningless) `Stroke` instead. `receive`/`send` only ever read
+        // the validated prefix, so the sentinel value is never observed.
+        [Stroke::Mouse {
+            state: MouseState::empty(),
+            flags: MouseFlags::empty(),
+            rolling: 0,
+            x: 0,
+            y: 0,
+            information: 0,
+        }; BUFFER_SIZE]
     }
 }
 
@@ -233,7 +253,7 @@ impl Interception {
     pub fn new() -> Option<Self> {
         let ctx = unsafe { raw::interception_create_context() };
 
-        if ctx == std::ptr::null_mut() {
+        if ctx.is_null() {
             return None;
         }
 
@@ -284,7 +304,9 @@ impl Interception {
 
     fn set_filter_internal(&self, predicate: Predicate, filter: u16) {
         unsafe {
-            let predicate = std::mem::transmute(Some(predicate));
+            let predicate = std::mem::transmute::<Option<Predicate>, raw::InterceptionPredicate>(
+                Some(predicate),
+            );
             raw::interception_set_filter(self.ctx, predicate, filter)
         }
     }
@@ -294,10 +316,7 @@ impl Interception {
     }
 
     pub fn wait_with_timeout(&self, duration: Duration) -> Device {
-        let millis = match u32::try_from(duration.as_millis()) {
-            Ok(m) => m,
-            Err(_) => u32::MAX,
-        };
+        let millis = duration.as_millis().min(u32::MAX as u128) as _;
 
         unsafe { raw::interception_wait_with_timeout(self.ctx, millis) }
     }
@@ -307,6 +326,20 @@ impl Interception {
         device: Device,
         strokes: &BoundedSlice<Stroke, BUFFER_SIZE>,
     ) -> i32 {
+        self.send_slice::<BUFFER_SIZE>(device, strokes)
+    }
+
+    /// [`StrokeBuffer`]-backed counterpart to [`Interception::send`]: sends
+    /// whatever strokes have been pushed into `buffer`.
+    pub fn send_buffered<const BUFFER_SIZE: usize>(
+        &self,
+        device: Device,
+        buffer: &StrokeBuffer<BUFFER_SIZE>,
+    ) -> i32 {
+        self.send_slice::<BUFFER_SIZE>(device, buffer.as_slice())
+    }
+
+    fn send_slice<const BUFFER_SIZE: usize>(&self, device: Device, strokes: &[Stroke]) -> i32 {
         if is_mouse(device) {
             self.send_internal::<raw::InterceptionMouseStroke, BUFFER_SIZE>(device, strokes)
         } else if is_keyboard(device) {
@@ -319,81 +352,86 @@ impl Interception {
     fn send_internal<T: TryFrom<Stroke>, const BUFFER_SIZE: usize>(
         &self,
         device: Device,
-        strokes: &BoundedSlice<Stroke, BUFFER_SIZE>,
+        strokes: &[Stroke],
     ) -> i32 {
-        let mut raw_strokes: [T; BUFFER_SIZE] = unsafe { MaybeUninit::uninit().assume_init() };
+        // Unlike `Stroke`, the raw FFI stroke types are POD, but we still
+        // avoid claiming the whole array is initialized: only the slots a
+        // valid conversion actually writes are ever read, via `len` below.
+        let mut raw_strokes: [MaybeUninit<T>; BUFFER_SIZE] =
+            unsafe { MaybeUninit::uninit().assume_init() };
         let mut len = 0usize;
-        for stroke in strokes.into_iter() {
+        for stroke in strokes.iter().take(BUFFER_SIZE) {
             if let Ok(raw_stroke) = T::try_from(*stroke) {
-                raw_strokes[len] = raw_stroke;
+                raw_strokes[len] = MaybeUninit::new(raw_stroke);
                 len += 1;
             }
         }
-        let raw_strokes = raw_strokes.get_prefix(len);
-        let ptr = raw_strokes.as_ptr();
-        unsafe { raw::interception_send(self.ctx, device, std::mem::transmute(ptr), len as u32) }
+        let ptr = raw_strokes.as_ptr().cast::<T>();
+        unsafe { raw::interception_send(self.ctx, device, ptr.cast(), len as u32) }
     }
 
-    pub fn receive<
-        's,
-        'buffer,
-        Buffer: InterceptionBuffer<BUFFER_SIZE>,
-        const BUFFER_SIZE: usize,
-    >(
-        &'s self,
+    /// Compatibility shim over [`Interception::receive_buffered`] for callers
+    /// still using the legacy, pre-filled `[Stroke; N]` buffer shape.
+    pub fn receive<'buffer, Buffer: InterceptionBuffer<BUFFER_SIZE>, const BUFFER_SIZE: usize>(
+        &self,
         device: Device,
         buffer: &'buffer mut Buffer,
     ) -> &'buffer BoundedSlice<Stroke, BUFFER_SIZE> {
-        let len = if is_mouse(device) {
-            self.receive_internal::<raw::InterceptionMouseStroke, Buffer, BUFFER_SIZE>(
+        let mut scratch = StrokeBuffer::<BUFFER_SIZE>::new();
+        let len = self.receive_buffered(device, &mut scratch).len();
+        for (i, stroke) in scratch.as_slice().iter().enumerate() {
+            buffer[i] = *stroke;
+        }
+        buffer.get_prefix(len)
+    }
+
+    /// Sound counterpart to [`Interception::receive`]: fills `buffer` with
+    /// the strokes read for `device` and hands back a slice over just the
+    /// strokes actually written, rather than over a fixed-size array that
+    /// had to be pre-filled with sentinel values.
+    pub fn receive_buffered<'buffer, const BUFFER_SIZE: usize>(
+        &self,
+        device: Device,
+        buffer: &'buffer mut StrokeBuffer<BUFFER_SIZE>,
+    ) -> &'buffer [Stroke] {
+        buffer.clear();
+        if is_mouse(device) {
+            self.receive_buffered_internal::<raw::InterceptionMouseStroke, BUFFER_SIZE>(
                 device, buffer,
-            )
+            );
         } else if is_keyboard(device) {
-            self.receive_internal::<raw::InterceptionKeyStroke, Buffer, BUFFER_SIZE>(device, buffer)
-        } else {
-            0
-        };
-        buffer.get_prefix(len)
+            self.receive_buffered_internal::<raw::InterceptionKeyStroke, BUFFER_SIZE>(
+                device, buffer,
+            );
+        }
+        buffer.as_slice()
     }
 
-    fn receive_internal<
-        's,
-        'buffer,
-        T: TryInto<Stroke> + Default + Copy,
-        Buffer: InterceptionBuffer<BUFFER_SIZE>,
-        const BUFFER_SIZE: usize,
-    >(
+    fn receive_buffered_internal<T: TryInto<Stroke> + Copy, const BUFFER_SIZE: usize>(
         &self,
         device: Device,
-        buffer: &'buffer mut Buffer,
-    ) -> usize {
-        let mut raw_strokes: [T; BUFFER_SIZE] = unsafe { MaybeUninit::uninit().assume_init() };
+        buffer: &mut StrokeBuffer<BUFFER_SIZE>,
+    ) {
+        let mut raw_strokes: [MaybeUninit<T>; BUFFER_SIZE] =
+            unsafe { MaybeUninit::uninit().assume_init() };
 
-        let ptr = raw_strokes.as_mut_ptr();
-        let len = match u32::try_from(raw_strokes.len()) {
-            Ok(l) => l,
-            Err(_) => u32::MAX,
-        };
+        let ptr = raw_strokes.as_mut_ptr().cast::<T>();
+        let len = u32::try_from(raw_strokes.len()).unwrap_or(u32::MAX);
 
-        let num_read =
-            unsafe { raw::interception_receive(self.ctx, device, std::mem::transmute(ptr), len) };
+        let num_read = unsafe { raw::interception_receive(self.ctx, device, ptr.cast(), len) };
 
-        let mut num_valid: usize = 0;
         for i in 0..num_read {
-            if let Ok(stroke) = raw_strokes[i as usize].try_into() {
-                buffer[num_valid as usize] = stroke;
-                num_valid += 1;
+            let raw_stroke = unsafe { raw_strokes[i as usize].assume_init() };
+            if let Ok(stroke) = raw_stroke.try_into() {
+                buffer.push(stroke);
             }
         }
-
-        num_valid
     }
 
     pub fn get_hardware_id(&mut self, device: Device) -> Option<String> {
         let ptr = self.text_buffer.as_mut_ptr();
-        let len = unsafe {
-            raw::interception_get_hardware_id(self.ctx, device, std::mem::transmute(ptr), 1024)
-        } as usize;
+        let len = unsafe { raw::interception_get_hardware_id(self.ctx, device, ptr.cast(), 1024) }
+            as usize;
         if len == 0 {
             return None;
         }
@@ -404,6 +442,110 @@ impl Interception {
                 .collect(),
         )
     }
+
+    /// Synthesizes the keyboard strokes needed to "type" `text` on a US
+    /// layout and sends them to `device`.
+    ///
+    /// Consecutive characters that need `LeftShift` held are coalesced into
+    /// a single shift press/release instead of toggling it per character.
+    /// Characters with no entry in [`keymap`] are skipped.
+    pub fn type_str(&self, device: Device, text: &str) {
+        const BUFFER_SIZE: usize = 256;
+
+        let mut buffer = StrokeBuffer::<BUFFER_SIZE>::new();
+        let mut shift_held = false;
+
+        macro_rules! push {
+            ($code:expr, $state:expr) => {{
+                if buffer.len() == BUFFER_SIZE {
+                    self.send_buffered(device, &buffer);
+                    buffer.clear();
+                }
+                buffer.push(Stroke::Keyboard {
+                    code: $code,
+                    state: $state,
+                    information: 0,
+                });
+            }};
+        }
+
+        let left_shift = match ScanCode::try_from(keymap::LEFT_SHIFT) {
+            Ok(code) => code,
+            Err(_) => return,
+        };
+
+        for c in text.chars() {
+            let (code, shift) = match keymap::lookup(c) {
+                Some(mapping) => mapping,
+                None => continue,
+            };
+            let code = match ScanCode::try_from(code) {
+                Ok(code) => code,
+                Err(_) => continue,
+            };
+
+            if shift && !shift_held {
+                push!(left_shift, KeyState::DOWN);
+                shift_held = true;
+            } else if !shift && shift_held {
+                push!(left_shift, KeyState::UP);
+                shift_held = false;
+            }
+
+            push!(code, KeyState::DOWN);
+            push!(code, KeyState::UP);
+        }
+
+        if shift_held {
+            push!(left_shift, KeyState::UP);
+        }
+
+        if !buffer.is_empty() {
+            self.send_buffered(device, &buffer);
+        }
+    }
+
+    /// Walks every device id the driver recognizes (`1..=INTERCEPTION_MAX_DEVICE`)
+    /// and reports what's attached at each one, so callers can pick a device by
+    /// its hardware id before they start intercepting.
+    pub fn devices(&mut self) -> impl Iterator<Item = DeviceInfo> + '_ {
+        (1..=raw::INTERCEPTION_MAX_DEVICE as Device).map(move |id| DeviceInfo {
+            id,
+            kind: DeviceKind::from_device(id),
+            hardware_id: self.get_hardware_id(id),
+            precedence: self.get_precedence(id),
+            filter: self.get_filter(id),
+        })
+    }
+}
+
+/// The coarse class of a device id, as reported by the driver.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DeviceKind {
+    Keyboard,
+    Mouse,
+    Invalid,
+}
+
+impl DeviceKind {
+    fn from_device(device: Device) -> Self {
+        if is_keyboard(device) {
+            DeviceKind::Keyboard
+        } else if is_mouse(device) {
+            DeviceKind::Mouse
+        } else {
+            DeviceKind::Invalid
+        }
+    }
+}
+
+/// Hardware metadata for a single device id, as returned by [`Interception::devices`].
+pub struct DeviceInfo {
+    pub id: Device,
+    pub kind: DeviceKind,
+    pub hardware_id: Option<String>,
+    pub precedence: Precedence,
+    pub filter: Filter,
 }
 
 impl Drop for Interception {
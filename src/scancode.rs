@@ -0,0 +1,220 @@
+//! PS/2 Set 1 scancodes, as reported in `InterceptionKeyStroke::code`.
+//!
+//! Each variant's discriminant is the raw scancode value, so `code as u16`
+//! round-trips back to exactly what the driver sent; [`ScanCode::try_from`]
+//! is the inverse, failing for codes this crate doesn't have a name for.
+
+use std::convert::TryFrom;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u16)]
+pub enum ScanCode {
+    Esc = 0x01,
+    Key1 = 0x02,
+    Key2 = 0x03,
+    Key3 = 0x04,
+    Key4 = 0x05,
+    Key5 = 0x06,
+    Key6 = 0x07,
+    Key7 = 0x08,
+    Key8 = 0x09,
+    Key9 = 0x0A,
+    Key0 = 0x0B,
+    Minus = 0x0C,
+    Equal = 0x0D,
+    Backspace = 0x0E,
+    Tab = 0x0F,
+    Q = 0x10,
+    W = 0x11,
+    E = 0x12,
+    R = 0x13,
+    T = 0x14,
+    Y = 0x15,
+    U = 0x16,
+    I = 0x17,
+    O = 0x18,
+    P = 0x19,
+    LeftBrace = 0x1A,
+    RightBrace = 0x1B,
+    Enter = 0x1C,
+    LeftCtrl = 0x1D,
+    A = 0x1E,
+    S = 0x1F,
+    D = 0x20,
+    F = 0x21,
+    G = 0x22,
+    H = 0x23,
+    J = 0x24,
+    K = 0x25,
+    L = 0x26,
+    Semicolon = 0x27,
+    Apostrophe = 0x28,
+    Grave = 0x29,
+    LeftShift = 0x2A,
+    Backslash = 0x2B,
+    Z = 0x2C,
+    X = 0x2D,
+    C = 0x2E,
+    V = 0x2F,
+    B = 0x30,
+    N = 0x31,
+    M = 0x32,
+    Comma = 0x33,
+    Dot = 0x34,
+    Slash = 0x35,
+    RightShift = 0x36,
+    KpAsterisk = 0x37,
+    LeftAlt = 0x38,
+    Space = 0x39,
+    CapsLock = 0x3A,
+    F1 = 0x3B,
+    F2 = 0x3C,
+    F3 = 0x3D,
+    F4 = 0x3E,
+    F5 = 0x3F,
+    F6 = 0x40,
+    F7 = 0x41,
+    F8 = 0x42,
+    F9 = 0x43,
+    F10 = 0x44,
+    NumLock = 0x45,
+    ScrollLock = 0x46,
+    Kp7 = 0x47,
+    Kp8 = 0x48,
+    Kp9 = 0x49,
+    KpMinus = 0x4A,
+    Kp4 = 0x4B,
+    Kp5 = 0x4C,
+    Kp6 = 0x4D,
+    KpPlus = 0x4E,
+    Kp1 = 0x4F,
+    Kp2 = 0x50,
+    Kp3 = 0x51,
+    Kp0 = 0x52,
+    KpDot = 0x53,
+    F11 = 0x57,
+    F12 = 0x58,
+}
+
+impl TryFrom<u16> for ScanCode {
+    type Error = &'static str;
+
+    fn try_from(code: u16) -> Result<Self, Self::Error> {
+        Ok(match code {
+            0x01 => ScanCode::Esc,
+            0x02 => ScanCode::Key1,
+            0x03 => ScanCode::Key2,
+            0x04 => ScanCode::Key3,
+            0x05 => ScanCode::Key4,
+            0x06 => ScanCode::Key5,
+            0x07 => ScanCode::Key6,
+            0x08 => ScanCode::Key7,
+            0x09 => ScanCode::Key8,
+            0x0A => ScanCode::Key9,
+            0x0B => ScanCode::Key0,
+            0x0C => ScanCode::Minus,
+            0x0D => ScanCode::Equal,
+            0x0E => ScanCode::Backspace,
+            0x0F => ScanCode::Tab,
+            0x10 => ScanCode::Q,
+            0x11 => ScanCode::W,
+            0x12 => ScanCode::E,
+            0x13 => ScanCode::R,
+            0x14 => ScanCode::T,
+            0x15 => ScanCode::Y,
+            0x16 => ScanCode::U,
+            0x17 => ScanCode::I,
+            0x18 => ScanCode::O,
+            0x19 => ScanCode::P,
+            0x1A => ScanCode::LeftBrace,
+            0x1B => ScanCode::RightBrace,
+            0x1C => ScanCode::Enter,
+            0x1D => ScanCode::LeftCtrl,
+            0x1E => ScanCode::A,
+            0x1F => ScanCode::S,
+            0x20 => ScanCode::D,
+            0x21 => ScanCode::F,
+            0x22 => ScanCode::G,
+            0x23 => ScanCode::H,
+            0x24 => ScanCode::J,
+            0x25 => ScanCode::K,
+            0x26 => ScanCode::L,
+            0x27 => ScanCode::Semicolon,
+            0x28 => ScanCode::Apostrophe,
+            0x29 => ScanCode::Grave,
+            0x2A => ScanCode::LeftShift,
+            0x2B => ScanCode::Backslash,
+            0x2C => ScanCode::Z,
+            0x2D => ScanCode::X,
+            0x2E => ScanCode::C,
+            0x2F => ScanCode::V,
+            0x30 => ScanCode::B,
+            0x31 => ScanCode::N,
+            0x32 => ScanCode::M,
+            0x33 => ScanCode::Comma,
+            0x34 => ScanCode::Dot,
+            0x35 => ScanCode::Slash,
+            0x36 => ScanCode::RightShift,
+            0x37 => ScanCode::KpAsterisk,
+            0x38 => ScanCode::LeftAlt,
+            0x39 => ScanCode::Space,
+            0x3A => ScanCode::CapsLock,
+            0x3B => ScanCode::F1,
+            0x3C => ScanCode::F2,
+            0x3D => ScanCode::F3,
+            0x3E => ScanCode::F4,
+            0x3F => ScanCode::F5,
+            0x40 => ScanCode::F6,
+            0x41 => ScanCode::F7,
+            0x42 => ScanCode::F8,
+            0x43 => ScanCode::F9,
+            0x44 => ScanCode::F10,
+            0x45 => ScanCode::NumLock,
+            0x46 => ScanCode::ScrollLock,
+            0x47 => ScanCode::Kp7,
+            0x48 => ScanCode::Kp8,
+            0x49 => ScanCode::Kp9,
+            0x4A => ScanCode::KpMinus,
+            0x4B => ScanCode::Kp4,
+            0x4C => ScanCode::Kp5,
+            0x4D => ScanCode::Kp6,
+            0x4E => ScanCode::KpPlus,
+            0x4F => ScanCode::Kp1,
+            0x50 => ScanCode::Kp2,
+            0x51 => ScanCode::Kp3,
+            0x52 => ScanCode::Kp0,
+            0x53 => ScanCode::KpDot,
+            0x57 => ScanCode::F11,
+            0x58 => ScanCode::F12,
+            _ => return Err("Unrecognized scancode"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_every_named_scancode() {
+        let codes = [
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+            0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C,
+            0x1D, 0x1E, 0x1F, 0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2A,
+            0x2B, 0x2C, 0x2D, 0x2E, 0x2F, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38,
+            0x39, 0x3A, 0x3B, 0x3C, 0x3D, 0x3E, 0x3F, 0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46,
+            0x47, 0x48, 0x49, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F, 0x50, 0x51, 0x52, 0x53, 0x57,
+            0x58,
+        ];
+
+        for code in codes {
+            let scancode = ScanCode::try_from(code).unwrap_or_else(|_| panic!("{:#04x}", code));
+            assert_eq!(scancode as u16, code);
+        }
+    }
+
+    #[test]
+    fn unrecognized_scancode_is_rejected() {
+        assert!(ScanCode::try_from(0xFF).is_err());
+    }
+}
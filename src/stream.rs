@@ -0,0 +1,121 @@
+//! Async `Stream` of strokes, gated behind the `futures` feature.
+//!
+//! `interception_wait` blocks the calling OS thread, so there is no way to
+//! poll it from an async executor directly. [`StrokeStream`] instead owns a
+//! background thread that loops on [`Interception::wait_with_timeout`] and
+//! [`Interception::receive_buffered`], forwarding whatever it reads over a
+//! bounded channel; the `Stream` side only ever polls that channel's
+//! receiver.
+//!
+//! This module is only compiled under `--features futures`, which pulls in
+//! `futures-core` and `futures-channel` as optional dependencies.
+
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use futures_channel::mpsc::{self, Receiver};
+use futures_core::Stream;
+
+use crate::{Device, Filter, Interception, Stroke, StrokeBuffer};
+
+/// Buffer size of the channel; with the single sender the channel holds up
+/// to one more `(Device, Stroke)` pair than this before the background
+/// thread starts dropping strokes instead of delivering them.
+const CHANNEL_CAPACITY: usize = 256;
+
+/// How long the background thread blocks in a single `wait_with_timeout`
+/// call before checking whether it has been asked to shut down.
+const POLL_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// A `Stream` of `(Device, Stroke)` pairs, backed by a dedicated polling
+/// thread.
+///
+/// Dropping a `StrokeStream` signals the background thread to stop and
+/// joins it, so no thread outlives the stream.
+pub struct StrokeStream {
+    receiver: Receiver<(Device, Stroke)>,
+    shutdown: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl StrokeStream {
+    /// Starts the background thread that feeds this stream. The context is
+    /// created and filtered on the background thread itself, so `Interception`
+    /// never has to cross a thread boundary (its driver handle's thread
+    /// affinity, if any, isn't something this crate can vouch for).
+    pub fn new(filters: &[Filter]) -> Option<Self> {
+        let filters: Vec<Filter> = filters.to_vec();
+
+        let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let worker_shutdown = Arc::clone(&shutdown);
+        let (ready_sender, ready_receiver) = std::sync::mpsc::channel();
+
+        let worker = std::thread::spawn(move || {
+            let ctx = match Interception::new() {
+                Some(ctx) => ctx,
+                None => {
+                    let _ = ready_sender.send(false);
+                    return;
+                }
+            };
+            for filter in &filters {
+                ctx.set_filter(*filter);
+            }
+            let _ = ready_sender.send(true);
+
+            let mut buffer = StrokeBuffer::<32>::new();
+            let mut sender = sender;
+            while !worker_shutdown.load(Ordering::Relaxed) {
+                let device = ctx.wait_with_timeout(POLL_TIMEOUT);
+                if device == 0 {
+                    continue;
+                }
+
+                for stroke in ctx.receive_buffered(device, &mut buffer) {
+                    if sender.try_send((device, *stroke)).is_err() {
+                        // Either the receiver was dropped (we're shutting
+                        // down) or the channel is full; either way, drop this
+                        // stroke and the rest of the batch rather than block
+                        // the capture thread.
+                        break;
+                    }
+                }
+            }
+        });
+
+        if !ready_receiver.recv().unwrap_or(false) {
+            let _ = worker.join();
+            return None;
+        }
+
+        Some(StrokeStream {
+            receiver,
+            shutdown,
+            worker: Some(worker),
+        })
+    }
+}
+
+impl Stream for StrokeStream {
+    type Item = (Device, Stroke);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl Drop for StrokeStream {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(worker) = self.worker.take() {
+            if let Err(panic) = worker.join() {
+                eprintln!("StrokeStream worker thread panicked: {:?}", panic);
+            }
+        }
+    }
+}